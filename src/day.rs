@@ -1,18 +1,23 @@
+use crate::profiling::{self, MemoryStats};
 use anyhow::Result;
 use std::time::{Duration, Instant};
 
 pub struct ExecutionResult<O: std::fmt::Display + Eq> {
     pub(crate) part_1_result: O,
     pub(crate) part_1_time: Duration,
+    pub(crate) part_1_memory: Option<MemoryStats>,
     pub(crate) part_2_result: O,
-    pub(crate) part_2_time: Duration
+    pub(crate) part_2_time: Duration,
+    pub(crate) part_2_memory: Option<MemoryStats>
 }
 
 pub struct DayResult {
     pub(crate) part_1_result: String,
     pub(crate) part_1_time: Duration,
+    pub(crate) part_1_memory: Option<MemoryStats>,
     pub(crate) part_2_result: String,
-    pub(crate) part_2_time: Duration
+    pub(crate) part_2_time: Duration,
+    pub(crate) part_2_memory: Option<MemoryStats>
 }
 
 impl<O: std::fmt::Display + Eq> From<ExecutionResult<O>> for DayResult {
@@ -20,28 +25,23 @@ impl<O: std::fmt::Display + Eq> From<ExecutionResult<O>> for DayResult {
         DayResult {
             part_1_result: result.part_1_result.to_string(),
             part_1_time: result.part_1_time,
+            part_1_memory: result.part_1_memory,
             part_2_result: result.part_2_result.to_string(),
             part_2_time: result.part_2_time,
+            part_2_memory: result.part_2_memory,
         }
     }
 }
 
-pub struct TestResult {
-    pub(crate) part_1_correct: bool,
-    pub(crate) part_1_time: Duration,
-    pub(crate) part_2_correct: bool,
-    pub(crate) part_2_time: Duration
+pub(crate) enum TestCaseResult {
+    NotExecuted,
+    Passed(Duration),
+    Failed(String, String)
 }
 
-impl TestResult {
-    fn from_execution_result<O: std::fmt::Display + Eq>(result: ExecutionResult<O>, expected_part_1: O, expected_part_2: O) -> Self {
-        TestResult {
-            part_1_correct: result.part_1_result == expected_part_1,
-            part_1_time: result.part_1_time,
-            part_2_correct: result.part_2_result == expected_part_2,
-            part_2_time: result.part_2_time,
-        }
-    }
+pub struct TestResult {
+    pub(crate) part1: TestCaseResult,
+    pub(crate) part2: TestCaseResult
 }
 
 pub trait DayImplementation {
@@ -53,27 +53,37 @@ pub trait DayImplementation {
     fn example_part_1_result(&self) -> Self::Output<'static>;
     fn example_part_2_result(&self) -> Self::Output<'static>;
 
+    /// Some days ship a different worked example for part 2. Override this
+    /// when `example_input()` isn't also a valid part 2 example.
+    fn example_part_2_input(&self) -> Option<&'static str> {
+        None
+    }
+
     fn execute_part_1<'a>(&self, input: &'a str) -> Result<(Self::Output<'a>, Self::Context<'a>)>;
     fn execute_part_2<'a>(&self, input: &'a str, context: Self::Context<'a>) -> Result<Self::Output<'a>>;
 
-    fn run_with_input<'a>(&self, input: &'a str) -> Result<ExecutionResult<Self::Output<'a>>> {
+    fn run_with_input<'a>(&self, input: &'a str, profile: bool) -> Result<ExecutionResult<Self::Output<'a>>> {
         log::debug!("Starting part 1 for day {}", self.day());
         let start_part_1 = Instant::now();
-        let (part_1_result, context) = self.execute_part_1(input)?;
+        let (part_1_outcome, part_1_memory) = profiling::measure(profile, || self.execute_part_1(input));
+        let (part_1_result, context) = part_1_outcome?;
         let part_1_time = start_part_1.elapsed();
         log::info!("Part 1 completed in {:?}, result: {}", part_1_time, part_1_result);
 
         log::debug!("Starting part 2 for day {}", self.day());
         let start_part_2 = Instant::now();
-        let part_2_result = self.execute_part_2(input, context)?;
+        let (part_2_outcome, part_2_memory) = profiling::measure(profile, || self.execute_part_2(input, context));
+        let part_2_result = part_2_outcome?;
         let part_2_time = start_part_2.elapsed();
         log::info!("Part 2 completed in {:?}, result: {}", part_2_time, part_2_result);
 
         Ok(ExecutionResult {
             part_1_result,
             part_1_time,
+            part_1_memory,
             part_2_result,
-            part_2_time
+            part_2_time,
+            part_2_memory
         })
     }
 }
@@ -82,7 +92,7 @@ pub trait Day {
     fn day(&self) -> u8;
 
     fn test_day(&self) -> Result<TestResult>;
-    fn execute_day(&self) -> Result<DayResult>;
+    fn execute_day(&self, input: &str, profile: bool) -> Result<DayResult>;
 }
 
 impl<T: DayImplementation> Day for T {
@@ -90,14 +100,45 @@ impl<T: DayImplementation> Day for T {
 
     fn test_day(&self) -> Result<TestResult> {
         log::info!("Running tests for day {}", self.day());
-        let result = self.run_with_input(DayImplementation::example_input(self))?;
-        Ok(TestResult::from_execution_result(result, DayImplementation::example_part_1_result(self), DayImplementation::example_part_2_result(self)))
+
+        log::debug!("Starting part 1 for day {}", self.day());
+        let start_part_1 = Instant::now();
+        let (part_1_result, context) = DayImplementation::execute_part_1(self, DayImplementation::example_input(self))?;
+        let part_1_time = start_part_1.elapsed();
+        log::info!("Part 1 completed in {:?}, result: {}", part_1_time, part_1_result);
+        let expected_part_1 = DayImplementation::example_part_1_result(self);
+        let part1 = if part_1_result == expected_part_1 {
+            TestCaseResult::Passed(part_1_time)
+        } else {
+            TestCaseResult::Failed(expected_part_1.to_string(), part_1_result.to_string())
+        };
+
+        let (part_2_input, context) = match DayImplementation::example_part_2_input(self) {
+            Some(part_2_input) => {
+                log::debug!("Day {} has a separate part 2 example; re-running part 1 against it to regenerate context", self.day());
+                let (_, part_2_context) = DayImplementation::execute_part_1(self, part_2_input)?;
+                (part_2_input, part_2_context)
+            }
+            None => (DayImplementation::example_input(self), context),
+        };
+
+        log::debug!("Starting part 2 for day {}", self.day());
+        let start_part_2 = Instant::now();
+        let part_2_result = DayImplementation::execute_part_2(self, part_2_input, context)?;
+        let part_2_time = start_part_2.elapsed();
+        log::info!("Part 2 completed in {:?}, result: {}", part_2_time, part_2_result);
+        let expected_part_2 = DayImplementation::example_part_2_result(self);
+        let part2 = if part_2_result == expected_part_2 {
+            TestCaseResult::Passed(part_2_time)
+        } else {
+            TestCaseResult::Failed(expected_part_2.to_string(), part_2_result.to_string())
+        };
+
+        Ok(TestResult { part1, part2 })
     }
 
-    fn execute_day(&self) -> Result<DayResult> {
-        // Temp hack before we implement input fetching
-        let input = self.example_input();
+    fn execute_day(&self, input: &str, profile: bool) -> Result<DayResult> {
         log::info!("Executing day {} with actual input", self.day());
-        Ok(DayResult::from(self.run_with_input(input)?))
+        Ok(DayResult::from(self.run_with_input(input, profile)?))
     }
 }
\ No newline at end of file
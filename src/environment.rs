@@ -1,18 +1,39 @@
+use crate::markdown;
 use anyhow::{Result, Context};
 use reqwest::blocking::Client;
-use reqwest::header::{COOKIE, USER_AGENT};
+use reqwest::header::{CONTENT_TYPE, COOKIE, USER_AGENT};
+use std::cell::RefCell;
 use std::io;
 use std::path::PathBuf;
 
 pub(crate) struct AOCEnvironment {
-    pub(crate) year: String,
+    year: RefCell<String>,
     inputs_dir: PathBuf,
+    puzzles_dir: PathBuf,
+    session_filename: PathBuf,
     session_cookie: String,
     http_client: Client
 }
 
+/// Result of POSTing an answer to adventofcode.com.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum SubmitOutcome {
+    /// The answer was correct.
+    Correct,
+    /// The answer was wrong, with an optional "too high"/"too low" hint.
+    Incorrect(Option<String>),
+    /// We're submitting too fast; AoC wants us to wait before trying again.
+    RateLimited(String),
+    /// AoC reports this part as already solved.
+    AlreadySolved,
+    /// The response didn't match any known pattern.
+    Unrecognized(String),
+}
+
 const SESSION_FILENAME: &str = "session";
+const SESSION_ENV_VAR: &str = "AOC_SESSION";
 const INPUT_DIRNAME: &str = "inputs";
+const PUZZLE_DIRNAME: &str = "puzzles";
 const AOC_BASE_URL: &str = "https://adventofcode.com";
 const USER_AGENT_STRING: &str = "github.com/ThePants999/advent-of-code-rust-runner by chris@chrispaterson.co.uk";
 
@@ -27,33 +48,71 @@ impl AOCEnvironment {
         }
         log::debug!("Using inputs directory at {:?}", inputs_dir);
 
+        let puzzles_dir = current_dir.join(PUZZLE_DIRNAME);
+        if !puzzles_dir.try_exists().context("Failed to check if puzzles directory exists")? {
+            log::info!("Creating puzzles directory at {:?}", puzzles_dir);
+            std::fs::create_dir(&puzzles_dir).context("Failed to create puzzles directory")?;
+        }
+        log::debug!("Using puzzles directory at {:?}", puzzles_dir);
+
         let session_filename = current_dir.join(SESSION_FILENAME);
         let session_value: String;
-        log::debug!("Checking for session file {:?}", session_filename);
-        if !session_filename.try_exists().context("Failed to check if session file exists")? {
-            log::info!("Session file not found, prompting for session cookie value");
-            println!("In order to download the inputs from the Advent of Code website, this program requires your session cookie.");
-            println!("Please log into the Advent of Code website, then check your browser cookies and enter the value of the 'session' cookie now.");
-            let mut input = String::new();
-            io::stdin().read_line(&mut input).context("Failed to read session cookie from stdin")?;
-            log::info!("Session cookie provided, saving to file");
-            session_value = input.trim().to_string();
-            std::fs::write(&session_filename, &session_value).context("Failed to write session file")?;
+        if let Ok(session_env) = std::env::var(SESSION_ENV_VAR) {
+            log::debug!("Using session cookie from {} environment variable", SESSION_ENV_VAR);
+            session_value = session_env.trim().to_string();
         } else {
-            log::debug!("Session file found, reading session cookie value");
-            session_value = std::fs::read_to_string(&session_filename).context("Failed to read session file")?.trim().to_string();
+            log::debug!("Checking for session file {:?}", session_filename);
+            if !session_filename.try_exists().context("Failed to check if session file exists")? {
+                log::info!("Session file not found, prompting for session cookie value");
+                println!("In order to download the inputs from the Advent of Code website, this program requires your session cookie.");
+                println!("Please log into the Advent of Code website, then check your browser cookies and enter the value of the 'session' cookie now.");
+                let mut input = String::new();
+                io::stdin().read_line(&mut input).context("Failed to read session cookie from stdin")?;
+                log::info!("Session cookie provided, saving to file");
+                session_value = input.trim().to_string();
+                std::fs::write(&session_filename, &session_value).context("Failed to write session file")?;
+            } else {
+                log::debug!("Session file found, reading session cookie value");
+                session_value = std::fs::read_to_string(&session_filename).context("Failed to read session file")?.trim().to_string();
+            }
         }
 
+        let http_client = Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .context("Failed to build HTTP client")?;
+
         Ok(AOCEnvironment {
-            year: year.to_string(),
+            year: RefCell::new(year.to_string()),
             inputs_dir,
+            puzzles_dir,
+            session_filename,
             session_cookie: format!("session={}", session_value),
-            http_client: Client::new()
+            http_client
         })
     }
 
+    pub(crate) fn year(&self) -> String {
+        self.year.borrow().clone()
+    }
+
+    pub(crate) fn set_year(&self, year: String) {
+        *self.year.borrow_mut() = year;
+    }
+
+    /// Directory that inputs/answers for the currently selected year are cached under.
+    fn year_inputs_dir(&self) -> Result<PathBuf> {
+        let dir = self.inputs_dir.join(self.year());
+        if !dir.try_exists().context("Failed to check if year inputs directory exists")? {
+            log::info!("Creating inputs directory for {} at {:?}", self.year(), dir);
+            std::fs::create_dir_all(&dir).context("Failed to create year inputs directory")?;
+        }
+        Ok(dir)
+    }
+
     pub(crate) fn fetch_input(&self, day: u8) -> Result<String> {
-        let input_filename = self.inputs_dir.join(format!("day{:02}", day));
+        let year_dir = self.year_inputs_dir()?;
+        let input_filename = year_dir.join(format!("day{:02}", day));
         log::debug!("Checking for input file for day {} at {:?}", day, input_filename);
         if input_filename.try_exists().context("Failed to check if input file exists")? {
             log::debug!("Input file found");
@@ -62,15 +121,17 @@ impl AOCEnvironment {
         }
 
         log::info!("Input file not found, fetching from Advent of Code website");
-        let url = format!("{}/{}/day/{}/input", AOC_BASE_URL, self.year, day);
+        let url = format!("{}/{}/day/{}/input", AOC_BASE_URL, self.year(), day);
         let response = self.http_client
             .get(&url)
             .header(COOKIE, &self.session_cookie)
             .header(USER_AGENT, USER_AGENT_STRING)
             .send()
             .context("Failed to send request for input")?;
-        if !response.status().is_success() {
-            anyhow::bail!("Failed to fetch input: HTTP {}", response.status());
+        let status = response.status();
+        self.check_session_status(status)?;
+        if !status.is_success() {
+            anyhow::bail!("Failed to fetch input: HTTP {}", status);
         }
         let input = response.text().context("Failed to read response text")?;
 
@@ -79,4 +140,169 @@ impl AOCEnvironment {
 
         Ok(input)
     }
+
+    pub(crate) fn fetch_puzzle(&self, day: u8) -> Result<String> {
+        let puzzle_filename = self.puzzles_dir.join(format!("day{:02}.md", day));
+        if puzzle_filename.try_exists().context("Failed to check if puzzle is cached")? {
+            let cached = std::fs::read_to_string(&puzzle_filename).context("Failed to read cached puzzle")?;
+            if cached.matches("## ").count() >= 2 {
+                log::debug!("Cached puzzle for day {} already has both parts", day);
+                return Ok(cached);
+            }
+            log::debug!("Cached puzzle for day {} only has part 1 so far; refetching in case part 2 has unlocked", day);
+        }
+
+        log::info!("Fetching puzzle prose for day {} from Advent of Code website", day);
+        let url = format!("{}/{}/day/{}", AOC_BASE_URL, self.year(), day);
+        let response = self.http_client
+            .get(&url)
+            .header(COOKIE, &self.session_cookie)
+            .header(USER_AGENT, USER_AGENT_STRING)
+            .send()
+            .context("Failed to send request for puzzle")?;
+        let status = response.status();
+        self.check_session_status(status)?;
+        if !status.is_success() {
+            anyhow::bail!("Failed to fetch puzzle: HTTP {}", status);
+        }
+        let html = response.text().context("Failed to read response text")?;
+        let puzzle = markdown::puzzle_to_markdown(&html);
+
+        log::info!("Saving puzzle prose to {:?}", puzzle_filename);
+        std::fs::write(&puzzle_filename, &puzzle).context("Failed to write puzzle file")?;
+
+        Ok(puzzle)
+    }
+
+    /// Creates an empty input file for `day` if one isn't already cached.
+    pub(crate) fn ensure_input_placeholder(&self, day: u8) -> Result<PathBuf> {
+        let year_dir = self.year_inputs_dir()?;
+        let input_filename = year_dir.join(format!("day{:02}", day));
+        if !input_filename.try_exists().context("Failed to check if input file exists")? {
+            std::fs::write(&input_filename, "").context("Failed to create placeholder input file")?;
+        }
+        Ok(input_filename)
+    }
+
+    pub(crate) fn submit_answer(&self, day: u8, part: u8, answer: &str) -> Result<SubmitOutcome> {
+        let answers_filename = self.answers_filename(day)?;
+        if let Some(cached) = Self::cached_answer(&answers_filename, part)? {
+            if cached == answer {
+                log::debug!("Answer for day {} part {} already known to be correct", day, part);
+                return Ok(SubmitOutcome::Correct);
+            }
+        }
+
+        log::info!("Submitting answer for day {} part {}", day, part);
+        let url = format!("{}/{}/day/{}/answer", AOC_BASE_URL, self.year(), day);
+        let body = format!("level={}&answer={}", part, answer);
+        let response = self.http_client
+            .post(&url)
+            .header(COOKIE, &self.session_cookie)
+            .header(USER_AGENT, USER_AGENT_STRING)
+            .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(body)
+            .send()
+            .context("Failed to send request to submit answer")?;
+        let status = response.status();
+        self.check_session_status(status)?;
+        if !status.is_success() {
+            anyhow::bail!("Failed to submit answer: HTTP {}", status);
+        }
+        let html = response.text().context("Failed to read response text")?;
+        let outcome = Self::parse_submit_response(&html);
+
+        if outcome == SubmitOutcome::Correct {
+            Self::cache_answer(&answers_filename, part, answer)?;
+        }
+
+        Ok(outcome)
+    }
+
+    fn parse_submit_response(html: &str) -> SubmitOutcome {
+        if html.contains("That's the right answer") {
+            SubmitOutcome::Correct
+        } else if html.contains("not the right answer") {
+            let hint = if html.contains("too high") {
+                Some("too high".to_string())
+            } else if html.contains("too low") {
+                Some("too low".to_string())
+            } else {
+                None
+            };
+            SubmitOutcome::Incorrect(hint)
+        } else if html.contains("you have to wait") || html.contains("too recently") {
+            let wait = html
+                .split("You have ")
+                .nth(1)
+                .and_then(|rest| rest.split(" left to wait").next())
+                .map(|wait| wait.trim().to_string())
+                .unwrap_or_else(|| "an unknown amount of time".to_string());
+            SubmitOutcome::RateLimited(wait)
+        } else if html.contains("already complete") || html.contains("Did you already") {
+            SubmitOutcome::AlreadySolved
+        } else {
+            SubmitOutcome::Unrecognized(html.to_string())
+        }
+    }
+
+    /// Bails with a clear error and clears the cached session file if `status` looks like an expired-cookie response.
+    fn check_session_status(&self, status: reqwest::StatusCode) -> Result<()> {
+        if status == reqwest::StatusCode::FOUND || status == reqwest::StatusCode::BAD_REQUEST || status == reqwest::StatusCode::UNAUTHORIZED {
+            let removed = self.invalidate_session().context("Failed to remove expired session file")?;
+            let next_step = if removed {
+                "The cached session file has been deleted; re-run the program to provide a fresh cookie."
+            } else {
+                "No cached session file was found to delete; supply a fresh cookie via the AOC_SESSION environment variable or the session file."
+            };
+            anyhow::bail!(
+                "Your Advent of Code session cookie appears to have expired (HTTP {}). {}",
+                status,
+                next_step
+            );
+        }
+        Ok(())
+    }
+
+    /// Deletes the cached session file so the next run prompts for a fresh cookie.
+    /// Returns whether a file was removed.
+    fn invalidate_session(&self) -> Result<bool> {
+        if self.session_filename.try_exists().context("Failed to check if session file exists")? {
+            std::fs::remove_file(&self.session_filename).context("Failed to remove cached session file")?;
+            log::info!("Removed cached session file at {:?}", self.session_filename);
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    fn answers_filename(&self, day: u8) -> Result<PathBuf> {
+        Ok(self.year_inputs_dir()?.join(format!("day{:02}.answers", day)))
+    }
+
+    fn cached_answer(answers_filename: &PathBuf, part: u8) -> Result<Option<String>> {
+        if !answers_filename.try_exists().context("Failed to check if answers cache exists")? {
+            return Ok(None);
+        }
+        let contents = std::fs::read_to_string(answers_filename).context("Failed to read answers cache")?;
+        Ok(contents
+            .lines()
+            .find_map(|line| line.strip_prefix(&format!("{}=", part)))
+            .map(|answer| answer.to_string()))
+    }
+
+    fn cache_answer(answers_filename: &PathBuf, part: u8, answer: &str) -> Result<()> {
+        let mut lines: Vec<String> = if answers_filename.try_exists().context("Failed to check if answers cache exists")? {
+            std::fs::read_to_string(answers_filename)
+                .context("Failed to read answers cache")?
+                .lines()
+                .filter(|line| !line.starts_with(&format!("{}=", part)))
+                .map(|line| line.to_string())
+                .collect()
+        } else {
+            Vec::new()
+        };
+        lines.push(format!("{}={}", part, answer));
+        std::fs::write(answers_filename, lines.join("\n") + "\n").context("Failed to write answers cache")?;
+        Ok(())
+    }
 }
\ No newline at end of file
@@ -1,4 +1,4 @@
-use std::{process, time::Duration};
+use std::{path::PathBuf, process, time::Duration};
 
 use anyhow::{anyhow, Context, Result};
 use clap::{CommandFactory, Parser};
@@ -6,9 +6,14 @@ use time::{Month, OffsetDateTime, UtcOffset};
 
 mod day;
 mod environment;
+mod markdown;
+mod profiling;
+mod scaffold;
 
 pub use day::{Day, DayImplementation};
 use day::TestCaseResult;
+use environment::SubmitOutcome;
+use profiling::MemoryStats;
 
 const DAY_SEPARATOR: &str = "-----------------------";
 
@@ -47,6 +52,34 @@ pub struct RunnerArgs {
         value_parser = clap::value_parser!(usize)
     )]
     pub num_runs: usize,
+
+    /// Submit the computed results to adventofcode.com
+    #[arg(long = "submit")]
+    pub submit: bool,
+
+    /// Generate a skeleton implementation for a new day
+    #[arg(
+        long = "scaffold",
+        value_parser = clap::value_parser!(u8),
+        conflicts_with_all = ["specific_day", "all_days", "submit"]
+    )]
+    pub scaffold: Option<u8>,
+
+    /// Print the puzzle prose for a day
+    #[arg(
+        long = "read",
+        value_parser = clap::value_parser!(u8),
+        conflicts_with_all = ["specific_day", "all_days", "submit", "scaffold"]
+    )]
+    pub read: Option<u8>,
+
+    /// Profile heap allocations per day (requires the `dhat-heap` feature)
+    #[arg(long = "profile")]
+    pub profile: bool,
+
+    /// Override the AoC year (defaults to the AOC_YEAR env var, then the configured year)
+    #[arg(long = "year")]
+    pub year: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -55,6 +88,7 @@ struct RunStats {
     max: Duration,
     median: Duration,
     mean: Duration,
+    peak_bytes: Option<u64>,
 }
 
 pub struct Runner {
@@ -88,6 +122,19 @@ impl Runner {
     }
 
     fn run_inner(&self, args: RunnerArgs) -> Result<()> {
+        self.resolve_year(&args);
+
+        if args.profile {
+            profiling::ensure_started();
+        }
+
+        if let Some(day) = args.scaffold {
+            return self.scaffold_day(day);
+        }
+        if let Some(day) = args.read {
+            return self.read_puzzle(day);
+        }
+
         let max_day = self.max_day()?;
         if let Some(day) = args.specific_day {
             if day == 0 || day > max_day {
@@ -111,12 +158,22 @@ impl Runner {
         }
     }
 
+    /// Resolves the year to run against: `--year`, then `AOC_YEAR`, then the
+    /// configured year the binary was built with.
+    fn resolve_year(&self, args: &RunnerArgs) {
+        if let Some(year) = &args.year {
+            self.env.set_year(year.clone());
+        } else if let Ok(year) = std::env::var("AOC_YEAR") {
+            self.env.set_year(year);
+        }
+    }
+
     fn current_aoc_day(&self) -> Option<u8> {
         let max_day = self.max_day().ok()?;
         // AoC is Eastern; use fixed UTC−5 to avoid extra dependencies.
         let offset = UtcOffset::from_hms(-5, 0, 0).ok()?;
         let now = OffsetDateTime::now_utc().to_offset(offset);
-        if now.year().to_string() == self.env.year && now.month() == Month::December {
+        if now.year().to_string() == self.env.year() && now.month() == Month::December {
             let today = now.day() as u8;
             (today <= max_day).then_some(today)
         } else {
@@ -126,12 +183,15 @@ impl Runner {
 
     fn run_all_days(&self, args: &RunnerArgs) -> Result<()> {
         let mut medians = Vec::with_capacity(self.days.len());
+        let mut peaks = Vec::with_capacity(self.days.len());
         let mut totals = RunStats::default();
         let mut max_time = Duration::ZERO;
+        let mut max_peak: u64 = 0;
 
         for (idx, day) in self.days.iter().enumerate() {
             let stats = self.run_day(day.as_ref(), args)?;
             medians.push(stats.median);
+            peaks.push(stats.peak_bytes.unwrap_or(0));
             totals.min += stats.min;
             totals.max += stats.max;
             totals.mean += stats.mean;
@@ -139,6 +199,11 @@ impl Runner {
             if stats.median > max_time {
                 max_time = stats.median;
             }
+            if let Some(peak) = stats.peak_bytes {
+                if peak > max_peak {
+                    max_peak = peak;
+                }
+            }
             println!("Day {} complete\n", idx + 1);
         }
 
@@ -153,12 +218,17 @@ impl Runner {
                 );
             }
 
+            let (bars, max_metric) = if args.profile && max_peak > 0 {
+                println!("Bar chart axis: peak heap bytes");
+                (peaks.iter().map(|p| *p as f64).collect::<Vec<_>>(), max_peak as f64)
+            } else {
+                (medians.iter().map(|t| t.as_secs_f64()).collect::<Vec<_>>(), max_time.as_secs_f64())
+            };
+
             for threshold in (1..=10).rev().map(|t| t as f32 / 10.0) {
                 print!("| ");
-                for t in &medians {
-                    if max_time.as_secs_f64() > 0.0
-                        && (t.as_secs_f64() / max_time.as_secs_f64()) >= threshold as f64
-                    {
+                for value in &bars {
+                    if max_metric > 0.0 && (value / max_metric) >= threshold as f64 {
                         print!("#");
                     } else {
                         print!(" ");
@@ -190,7 +260,7 @@ impl Runner {
             return Err(anyhow!(
                 "Day {} is not valid for {} (max {})",
                 day_impl.day(),
-                self.env.year,
+                self.env.year(),
                 max_day
             ));
         }
@@ -203,7 +273,10 @@ impl Runner {
             }
         }
 
-        if !args.skip_tests {
+        if args.skip_tests {
+            self.print_test_result("Part 1", &TestCaseResult::NotExecuted);
+            self.print_test_result("Part 2", &TestCaseResult::NotExecuted);
+        } else {
             match day_impl.test_day() {
                 Ok(test_result) => {
                     self.print_test_result("Part 1", &test_result.part1);
@@ -224,18 +297,23 @@ impl Runner {
 
         if args.num_runs < 2 {
             let res = day_impl
-                .execute_day(input.as_str())
+                .execute_day(input.as_str(), args.profile)
                 .with_context(|| format!("Day {} execution failed", day_impl.day()))?;
             let total = res.part_1_time + res.part_2_time;
             println!(
                 "Part 1 real: {} ({:?})\nPart 2 real: {} ({:?})\nTotal time: {:?}",
                 res.part_1_result, res.part_1_time, res.part_2_result, res.part_2_time, total
             );
+            self.print_memory(&res.part_1_memory, &res.part_2_memory);
+            if args.submit {
+                self.submit_results(day_impl.day(), &res)?;
+            }
             return Ok(RunStats {
                 min: total,
                 max: total,
                 median: total,
                 mean: total,
+                peak_bytes: peak_of(&res.part_1_memory, &res.part_2_memory),
             });
         }
 
@@ -243,7 +321,7 @@ impl Runner {
         for _ in 0..args.num_runs {
             results.push(
                 day_impl
-                    .execute_day(input.as_str())
+                    .execute_day(input.as_str(), args.profile)
                     .with_context(|| format!("Day {} execution failed", day_impl.day()))?,
             );
         }
@@ -253,11 +331,13 @@ impl Runner {
 
         let p1_stats = build_stats(&mut p1_times);
         let p2_stats = build_stats(&mut p2_times);
+        let peak_bytes = peak_of(&results[0].part_1_memory, &results[0].part_2_memory);
         let totals = RunStats {
             min: p1_stats.min + p2_stats.min,
             max: p1_stats.max + p2_stats.max,
             median: p1_stats.median + p2_stats.median,
             mean: p1_stats.mean + p2_stats.mean,
+            peak_bytes,
         };
 
         println!(
@@ -272,10 +352,56 @@ impl Runner {
             "Total time: median {:?}, mean {:?}, min {:?}, max {:?}",
             totals.median, totals.mean, totals.min, totals.max
         );
+        self.print_memory(&results[0].part_1_memory, &results[0].part_2_memory);
+
+        if args.submit {
+            self.submit_results(day_impl.day(), &results[0])?;
+        }
 
         Ok(totals)
     }
 
+    fn print_memory(&self, part_1_memory: &Option<MemoryStats>, part_2_memory: &Option<MemoryStats>) {
+        if let Some(m) = part_1_memory {
+            println!(
+                "Part 1 memory: peak {} bytes, total {} bytes, {} allocations",
+                m.peak_bytes, m.total_bytes, m.allocations
+            );
+        }
+        if let Some(m) = part_2_memory {
+            println!(
+                "Part 2 memory: peak {} bytes, total {} bytes, {} allocations",
+                m.peak_bytes, m.total_bytes, m.allocations
+            );
+        }
+    }
+
+    fn submit_results(&self, day: u8, result: &day::DayResult) -> Result<()> {
+        let part_1_outcome = self.submit_part(day, 1, &result.part_1_result)?;
+        if matches!(part_1_outcome, SubmitOutcome::Correct | SubmitOutcome::AlreadySolved) {
+            self.submit_part(day, 2, &result.part_2_result)?;
+        } else {
+            println!("Part 2: not submitted, part 1 isn't solved yet");
+        }
+        Ok(())
+    }
+
+    fn submit_part(&self, day: u8, part: u8, answer: &str) -> Result<SubmitOutcome> {
+        let outcome = self
+            .env
+            .submit_answer(day, part, answer)
+            .with_context(|| format!("Failed to submit day {} part {}", day, part))?;
+        match &outcome {
+            SubmitOutcome::Correct => println!("Part {part}: submitted, CORRECT"),
+            SubmitOutcome::Incorrect(Some(hint)) => println!("Part {part}: submitted, INCORRECT ({hint})"),
+            SubmitOutcome::Incorrect(None) => println!("Part {part}: submitted, INCORRECT"),
+            SubmitOutcome::RateLimited(wait) => println!("Part {part}: not submitted, rate limited ({wait} left to wait)"),
+            SubmitOutcome::AlreadySolved => println!("Part {part}: already solved, skipping"),
+            SubmitOutcome::Unrecognized(_) => println!("Part {part}: submitted, but the response couldn't be parsed"),
+        }
+        Ok(outcome)
+    }
+
     fn print_test_result(&self, label: &str, result: &TestCaseResult) {
         match result {
             TestCaseResult::NotExecuted => println!("{label} test: (skipped)"),
@@ -288,16 +414,70 @@ impl Runner {
         }
     }
 
-    fn max_day(&self) -> Result<u8> {
-        let year: u32 = self
+    fn scaffold_day(&self, day: u8) -> Result<()> {
+        let max_day = self.max_day()?;
+        if day == 0 || day > max_day {
+            return Err(anyhow!("Day must be between 1 and {}", max_day));
+        }
+
+        let days_dir = PathBuf::from("src").join("days");
+        if !days_dir.try_exists().context("Failed to check if days directory exists")? {
+            std::fs::create_dir_all(&days_dir).context("Failed to create days directory")?;
+        }
+        let day_file = days_dir.join(format!("day{:02}.rs", day));
+        if day_file.try_exists().context("Failed to check if day source already exists")? {
+            return Err(anyhow!("{} already exists", day_file.display()));
+        }
+        std::fs::write(&day_file, scaffold::day_template(day)).context("Failed to write day source")?;
+        println!("Created {}", day_file.display());
+
+        match self.env.fetch_input(day) {
+            Ok(_) => println!("Downloaded input for day {day}"),
+            Err(e) => {
+                log::warn!("Failed to download input for day {}: {}", day, e);
+                let placeholder = self.env.ensure_input_placeholder(day)?;
+                println!(
+                    "Couldn't download input for day {day} yet ({e}); created an empty placeholder at {}",
+                    placeholder.display()
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    fn read_puzzle(&self, day: u8) -> Result<()> {
+        let max_day = self.max_day()?;
+        if day == 0 || day > max_day {
+            return Err(anyhow!("Day must be between 1 and {}", max_day));
+        }
+
+        let puzzle = self
             .env
-            .year
+            .fetch_puzzle(day)
+            .with_context(|| format!("Failed to fetch puzzle prose for day {}", day))?;
+        println!("{puzzle}");
+        Ok(())
+    }
+
+    fn max_day(&self) -> Result<u8> {
+        let year_str = self.env.year();
+        let year: u32 = year_str
             .parse()
-            .with_context(|| format!("Invalid year value {}", self.env.year))?;
+            .with_context(|| format!("Invalid year value {}", year_str))?;
         Ok(if year >= 2025 { 12 } else { 25 })
     }
 }
 
+fn peak_of(part_1_memory: &Option<MemoryStats>, part_2_memory: &Option<MemoryStats>) -> Option<u64> {
+    if part_1_memory.is_none() && part_2_memory.is_none() {
+        return None;
+    }
+    let part_1_peak = part_1_memory.map_or(0, |m| m.peak_bytes);
+    let part_2_peak = part_2_memory.map_or(0, |m| m.peak_bytes);
+    Some(part_1_peak.max(part_2_peak))
+}
+
 fn build_stats(times: &mut [Duration]) -> RunStats {
     if times.is_empty() {
         return RunStats::default();
@@ -317,5 +497,6 @@ fn build_stats(times: &mut [Duration]) -> RunStats {
         max,
         median,
         mean,
+        peak_bytes: None,
     }
 }
@@ -0,0 +1,77 @@
+/// Extracts the `<article class="day-desc">...</article>` blocks from an AoC
+/// problem page and renders them as Markdown.
+pub(crate) fn puzzle_to_markdown(html: &str) -> String {
+    extract_articles(html)
+        .iter()
+        .map(|article| html_to_markdown(article))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+fn extract_articles(html: &str) -> Vec<&str> {
+    const OPEN_TAG: &str = "<article class=\"day-desc\">";
+    const CLOSE_TAG: &str = "</article>";
+
+    let mut articles = Vec::new();
+    let mut rest = html;
+    while let Some(start) = rest.find(OPEN_TAG) {
+        let body_start = start + OPEN_TAG.len();
+        if let Some(end) = rest[body_start..].find(CLOSE_TAG) {
+            articles.push(&rest[body_start..body_start + end]);
+            rest = &rest[body_start + end + CLOSE_TAG.len()..];
+        } else {
+            break;
+        }
+    }
+    articles
+}
+
+fn html_to_markdown(html: &str) -> String {
+    let markdown = strip_links(html)
+        .replace("<h2>", "## ")
+        .replace("</h2>", "\n")
+        .replace("<em class=\"star\">", "*")
+        .replace("<p>", "")
+        .replace("</p>", "\n\n")
+        .replace("<ul>", "")
+        .replace("</ul>", "\n")
+        .replace("<li>", "- ")
+        .replace("</li>", "\n")
+        .replace("<pre>", "")
+        .replace("</pre>", "")
+        .replace("<code>", "`")
+        .replace("</code>", "`")
+        .replace("<em>", "*")
+        .replace("</em>", "*")
+        .replace("<b>", "**")
+        .replace("</b>", "**")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'");
+
+    markdown.trim().to_string()
+}
+
+fn strip_links(html: &str) -> String {
+    const OPEN_TAG_PREFIX: &str = "<a ";
+    const CLOSE_TAG: &str = "</a>";
+
+    let mut result = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(start) = rest.find(OPEN_TAG_PREFIX) {
+        result.push_str(&rest[..start]);
+        let Some(tag_end) = rest[start..].find('>') else {
+            break;
+        };
+        let body_start = start + tag_end + 1;
+        let Some(close) = rest[body_start..].find(CLOSE_TAG) else {
+            break;
+        };
+        result.push_str(&rest[body_start..body_start + close]);
+        rest = &rest[body_start + close + CLOSE_TAG.len()..];
+    }
+    result.push_str(rest);
+    result
+}
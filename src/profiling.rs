@@ -0,0 +1,52 @@
+/// Per-part heap allocation stats, captured with `dhat` when `--profile` is
+/// passed and the crate is built with the `dhat-heap` feature.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct MemoryStats {
+    pub(crate) peak_bytes: u64,
+    pub(crate) total_bytes: u64,
+    pub(crate) allocations: u64,
+}
+
+#[cfg(feature = "dhat-heap")]
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+#[cfg(feature = "dhat-heap")]
+static PROFILER: std::sync::OnceLock<dhat::Profiler> = std::sync::OnceLock::new();
+
+pub(crate) fn ensure_started() {
+    #[cfg(feature = "dhat-heap")]
+    {
+        PROFILER.get_or_init(dhat::Profiler::new_heap);
+    }
+}
+
+/// Runs `f`, optionally capturing the heap activity it causes. Returns
+/// `None` for the stats whenever `profile` is false or the `dhat-heap`
+/// feature isn't compiled in.
+pub(crate) fn measure<T>(profile: bool, f: impl FnOnce() -> T) -> (T, Option<MemoryStats>) {
+    if !profile {
+        return (f(), None);
+    }
+
+    #[cfg(feature = "dhat-heap")]
+    {
+        // The profiler is installed once per process (see `ensure_started`);
+        // diff stats around `f` rather than restarting it, since dhat panics
+        // if a second `Profiler` is created while one is still running.
+        let before = dhat::HeapStats::get();
+        let result = f();
+        let after = dhat::HeapStats::get();
+        let memory = MemoryStats {
+            peak_bytes: after.max_bytes.saturating_sub(before.curr_bytes) as u64,
+            total_bytes: after.total_bytes.saturating_sub(before.total_bytes) as u64,
+            allocations: after.total_blocks.saturating_sub(before.total_blocks) as u64,
+        };
+        (result, Some(memory))
+    }
+
+    #[cfg(not(feature = "dhat-heap"))]
+    {
+        (f(), None)
+    }
+}
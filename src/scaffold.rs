@@ -0,0 +1,40 @@
+/// Generates the skeleton source for a new day's `DayImplementation`.
+pub(crate) fn day_template(day: u8) -> String {
+    format!(
+        r#"use aoc_runner::DayImplementation;
+use anyhow::Result;
+
+pub struct Day{day:02};
+
+impl DayImplementation for Day{day:02} {{
+    type Output<'a> = u64;
+    type Context<'a> = ();
+
+    fn day(&self) -> u8 {{
+        {day}
+    }}
+
+    fn example_input(&self) -> &'static str {{
+        ""
+    }}
+
+    fn example_part_1_result(&self) -> Self::Output<'static> {{
+        todo!()
+    }}
+
+    fn example_part_2_result(&self) -> Self::Output<'static> {{
+        todo!()
+    }}
+
+    fn execute_part_1<'a>(&self, input: &'a str) -> Result<(Self::Output<'a>, Self::Context<'a>)> {{
+        todo!()
+    }}
+
+    fn execute_part_2<'a>(&self, input: &'a str, context: Self::Context<'a>) -> Result<Self::Output<'a>> {{
+        todo!()
+    }}
+}}
+"#,
+        day = day
+    )
+}